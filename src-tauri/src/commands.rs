@@ -1,8 +1,10 @@
-use log::{info, warn, error};
+use tracing::{info, warn, error, instrument};
 use serde_json::Value;
 use std::path::PathBuf;
-use tauri::{State, Manager};
+use tauri::{AppHandle, State, Manager};
 
+use crate::jobs;
+use crate::rules::FileInfo;
 use crate::types::*;
 use crate::AppState;
 
@@ -28,12 +30,40 @@ pub async fn check_health(state: State<'_, AppState>) -> Result<HealthResponse,
 #[tauri::command]
 pub async fn start_python_backend(state: State<'_, AppState>) -> Result<String, String> {
     info!("Starting Python backend requested from frontend");
-    
+
+    let settings = state.settings.get().await;
+    if settings.backend_mode == BackendMode::Remote {
+        let mut bridge = state.python_bridge.lock().await;
+        bridge.set_base_url(settings.remote_backend_url.clone());
+    }
+
+    let backend_mode = settings.backend_mode.clone();
     let mut process_manager = state.process_manager.lock().await;
+    process_manager.configure_backend(settings.backend_mode, settings.remote_backend_url);
     match process_manager.start_python_backend().await {
         Ok(_) => {
-            info!("Python backend started successfully");
-            Ok("Backend started successfully".to_string())
+            // Local mode may have picked a different port than configured
+            // if the default was already taken.
+            let local_port = (backend_mode == BackendMode::Local).then(|| process_manager.get_port());
+            drop(process_manager);
+
+            // The process reports "started" as soon as it's spawned; wait
+            // until the bridge can actually reach it before telling the
+            // frontend it's safe to issue requests.
+            let mut bridge = state.python_bridge.lock().await;
+            if let Some(port) = local_port {
+                bridge.set_base_url(format!("http://127.0.0.1:{}", port));
+            }
+            match bridge.wait_for_ready(std::time::Duration::from_secs(30)).await {
+                Ok(_) => {
+                    info!("Python backend started successfully");
+                    Ok("Backend started successfully".to_string())
+                }
+                Err(e) => {
+                    error!("Python backend spawned but never became ready: {}", e);
+                    Err(format!("Backend spawned but never became ready: {}", e))
+                }
+            }
         }
         Err(e) => {
             error!("Failed to start Python backend: {}", e);
@@ -59,10 +89,16 @@ pub async fn stop_python_backend(state: State<'_, AppState>) -> Result<String, S
     }
 }
 
+#[tauri::command]
+pub async fn get_backend_logs(lines: usize, state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    let process_manager = state.process_manager.lock().await;
+    Ok(process_manager.recent_backend_logs(lines).await)
+}
+
 #[tauri::command]
 pub async fn get_backend_status(state: State<'_, AppState>) -> Result<ProcessStatus, String> {
     let mut process_manager = state.process_manager.lock().await;
-    let mut status = process_manager.get_backend_status();
+    let mut status = process_manager.get_backend_status().await;
     
     // Try to get health status if process is running
     if status.is_running {
@@ -78,6 +114,7 @@ pub async fn get_backend_status(state: State<'_, AppState>) -> Result<ProcessSta
 // ===== Project Analysis Commands =====
 
 #[tauri::command]
+#[instrument(skip(state), fields(path = %path))]
 pub async fn scan_project_directory(
     path: String,
     use_ai: bool,
@@ -85,18 +122,27 @@ pub async fn scan_project_directory(
     state: State<'_, AppState>
 ) -> Result<ScanResponse, String> {
     info!("Scanning project directory: {}", path);
-    
+
+    // Fall back to the configured default AI model when the caller wants AI
+    // classification but didn't name one explicitly.
+    let settings = state.settings.get().await;
+    let ai_model = ai_model.or_else(|| use_ai.then(|| settings.default_ai_model.clone()));
+
     let request = ScanRequest {
         path,
         use_ai,
         ai_model,
         max_files: 10000,
     };
-    
+
     let bridge = state.python_bridge.lock().await;
     match bridge.scan_project_directory(request).await {
-        Ok(response) => {
+        Ok(mut response) => {
             info!("Project scan completed successfully");
+            apply_classification_rules(&state, &mut response).await;
+            if settings.enable_workflow_metrics {
+                crate::telemetry::record_scan(&response);
+            }
             Ok(response)
         }
         Err(e) => {
@@ -106,6 +152,40 @@ pub async fn scan_project_directory(
     }
 }
 
+/// Run the user's classification rules against the scan result, replacing
+/// `final_classification` with the first non-empty category a rule returns.
+/// Runs after the AI/heuristic path so rules always have the last word.
+/// Rules can only force a category; there is no way for a rule to veto the
+/// backend's category without naming a replacement.
+async fn apply_classification_rules(state: &State<'_, AppState>, response: &mut ScanResponse) {
+    let file_info = FileInfo {
+        path: response.path.clone(),
+        extension: std::path::Path::new(&response.path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_string(),
+        detected_language: response.final_classification.category.clone(),
+        size_bytes: 0,
+    };
+
+    let engine = state.rule_engine.lock().await;
+    match engine.classify(&file_info) {
+        Ok(Some(category)) => {
+            info!("Classification rule overrode category to: {}", category);
+            response.final_classification = ClassificationResult {
+                category,
+                confidence: 1.0,
+                reasoning: "Overridden by user classification rule".to_string(),
+                method: "rule".to_string(),
+                suggested_name: response.final_classification.suggested_name.clone(),
+            };
+        }
+        Ok(None) => {}
+        Err(e) => warn!("Classification rules failed to evaluate, keeping backend result: {}", e),
+    }
+}
+
 #[tauri::command]
 pub async fn get_ollama_models(state: State<'_, AppState>) -> Result<Vec<String>, String> {
     info!("Fetching Ollama models");
@@ -126,6 +206,7 @@ pub async fn get_ollama_models(state: State<'_, AppState>) -> Result<Vec<String>
 // ===== Organization Commands =====
 
 #[tauri::command]
+#[instrument(skip(state, target_category, conflict_resolution, create_backup, custom_name), fields(scan_id = %scan_id))]
 pub async fn preview_organization(
     scan_id: String,
     target_category: Option<String>,
@@ -135,19 +216,23 @@ pub async fn preview_organization(
     state: State<'_, AppState>
 ) -> Result<OrganizePreviewResponse, String> {
     info!("Generating organization preview for scan: {}", scan_id);
-    
+
+    let settings = state.settings.get().await;
     let request = OrganizePreviewRequest {
         scan_id,
         target_category,
-        conflict_resolution: conflict_resolution.unwrap_or_else(|| "rename".to_string()),
-        create_backup: create_backup.unwrap_or(true),
+        conflict_resolution: conflict_resolution.unwrap_or_else(|| settings.conflict_resolution_strategy.clone()),
+        create_backup: create_backup.unwrap_or(settings.create_backup_by_default),
         custom_name,
     };
-    
+
     let bridge = state.python_bridge.lock().await;
     match bridge.preview_organization(request).await {
         Ok(response) => {
             info!("Organization preview generated successfully");
+            if settings.enable_workflow_metrics {
+                crate::telemetry::record_organize_preview(&response);
+            }
             Ok(response)
         }
         Err(e) => {
@@ -158,31 +243,38 @@ pub async fn preview_organization(
 }
 
 #[tauri::command]
+#[instrument(skip(app_handle, state), fields(plan_id = %plan_id))]
 pub async fn execute_organization(
     plan_id: String,
     confirm_execution: bool,
+    app_handle: AppHandle,
     state: State<'_, AppState>
 ) -> Result<OrganizeExecuteResponse, String> {
     info!("Executing organization plan: {}", plan_id);
-    
+
     if !confirm_execution {
         return Err("Execution requires explicit confirmation".to_string());
     }
-    
+
     let request = OrganizeExecuteRequest {
-        plan_id,
+        plan_id: plan_id.clone(),
         confirm_execution,
     };
-    
-    let bridge = state.python_bridge.lock().await;
-    match bridge.execute_organization(request).await {
+
+    // Execution is handed off to a background worker so this command returns
+    // as soon as the run has been accepted; progress streams separately via
+    // the `organization-progress` event and `get_organization_progress`.
+    match jobs::spawn_organization_job(app_handle, request).await {
         Ok(response) => {
-            info!("Organization execution completed: {}", response.status);
+            info!("Organization run {} started for plan {}", response.operation_id, plan_id);
+            if state.settings.get().await.enable_workflow_metrics {
+                crate::telemetry::record_organize_execute(&response);
+            }
             Ok(response)
         }
         Err(e) => {
-            error!("Organization execution failed: {}", e);
-            Err(format!("Organization execution failed: {}", e))
+            error!("Failed to start organization run: {}", e);
+            Err(e)
         }
     }
 }
@@ -190,45 +282,68 @@ pub async fn execute_organization(
 #[tauri::command]
 pub async fn get_organization_progress(
     operation_id: String,
-    _state: State<'_, AppState>
+    state: State<'_, AppState>
 ) -> Result<OperationProgress, String> {
-    // This would typically query the backend for progress
-    // For now, return a placeholder since progress tracking would need WebSockets
-    warn!("Progress tracking not fully implemented yet for operation: {}", operation_id);
-    
-    // Return a basic progress structure
-    Ok(OperationProgress {
-        operation_id,
-        current_step: 1,
-        total_steps: 1,
-        current_operation: "Progress tracking not implemented".to_string(),
-        files_processed: 0,
-        total_files: 0,
-        bytes_processed: 0,
-        total_bytes: 0,
-        elapsed_time_seconds: 0.0,
-        estimated_remaining_seconds: 0.0,
-        status: "unknown".to_string(),
-        error_message: Some("Progress tracking not implemented yet".to_string()),
-    })
+    match state.job_queue.get_progress(&operation_id).await {
+        Some(progress) => Ok(progress),
+        None => {
+            warn!("No progress recorded for operation: {}", operation_id);
+            Err(format!("Unknown operation: {}", operation_id))
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn cancel_organization(
+    operation_id: String,
+    state: State<'_, AppState>
+) -> Result<String, String> {
+    info!("Cancelling organization run: {}", operation_id);
+
+    if state.job_queue.cancel(&operation_id).await {
+        Ok("Cancellation requested".to_string())
+    } else {
+        Err(format!("No running operation found for id: {}", operation_id))
+    }
+}
+
+/// Replay a rollback manifest, restoring every file move it marked complete.
+/// Safe to call again on a manifest that's already been partially rolled
+/// back (e.g. after a crash mid-replay), including on app startup.
+#[tauri::command]
+#[instrument(skip(app_handle), fields(manifest_path = %request.manifest_path))]
+pub async fn rollback_organization(
+    request: RollbackRequest,
+    app_handle: AppHandle,
+) -> Result<RollbackResponse, String> {
+    info!("Rollback requested for manifest: {}", request.manifest_path);
+
+    match jobs::spawn_rollback_job(app_handle, request).await {
+        Ok(response) => {
+            info!("Rollback finished with status: {}", response.status);
+            Ok(response)
+        }
+        Err(e) => {
+            error!("Rollback failed: {}", e);
+            Err(format!("Rollback failed: {}", e))
+        }
+    }
 }
 
 // ===== File System Commands =====
 
 #[tauri::command]
-pub async fn select_directory() -> Result<Option<String>, String> {
-    use tauri::api::dialog::blocking::FileDialogBuilder;
-    
+pub async fn select_directory(app_handle: AppHandle) -> Result<Option<String>, String> {
+    use tauri_plugin_dialog::DialogExt;
+
     info!("Opening directory selection dialog");
-    
-    let result = FileDialogBuilder::new()
-        .set_title("Select Project Directory")
-        .pick_folder();
-    
+
+    let result = app_handle.dialog().file().set_title("Select Project Directory").blocking_pick_folder();
+
     match result {
         Some(path) => {
             info!("Directory selected: {:?}", path);
-            Ok(Some(path.to_string_lossy().to_string()))
+            Ok(Some(path.to_string()))
         }
         None => {
             info!("Directory selection cancelled");
@@ -248,19 +363,32 @@ pub async fn get_home_directory() -> Result<String, String> {
 // ===== Settings Commands =====
 
 #[tauri::command]
-pub async fn get_app_settings() -> Result<AppSettings, String> {
-    // For now, return default settings
-    // In a real app, this would load from a config file
+pub async fn get_app_settings(state: State<'_, AppState>) -> Result<AppSettings, String> {
     info!("Loading application settings");
-    Ok(AppSettings::default())
+    Ok(state.settings.get().await)
 }
 
 #[tauri::command]
-pub async fn save_app_settings(settings: AppSettings) -> Result<String, String> {
-    // For now, just log the settings
-    // In a real app, this would save to a config file
+pub async fn save_app_settings(settings: AppSettings, state: State<'_, AppState>) -> Result<String, String> {
     info!("Saving application settings: {:?}", settings);
-    Ok("Settings saved successfully".to_string())
+    match state.settings.save(settings.clone()).await {
+        Ok(_) => {
+            // Live-apply the new retry/backend settings so they take effect
+            // without a restart, same as the hot-reload watcher does for
+            // settings edited by hand on disk.
+            let mut bridge = state.python_bridge.lock().await;
+            if settings.backend_mode == BackendMode::Remote {
+                bridge.set_base_url(settings.remote_backend_url.clone());
+            }
+            crate::settings::apply_to_bridge(&settings, &mut bridge);
+
+            Ok("Settings saved successfully".to_string())
+        }
+        Err(e) => {
+            error!("Failed to save application settings: {}", e);
+            Err(format!("Failed to save settings: {}", e))
+        }
+    }
 }
 
 // ===== Project Creation Commands =====
@@ -317,10 +445,12 @@ pub async fn get_project_templates(state: State<'_, AppState>) -> Result<Vec<Pro
 // ===== Utility Commands =====
 
 #[tauri::command]
-pub async fn open_external_url(url: String) -> Result<(), String> {
+pub async fn open_external_url(url: String, app_handle: AppHandle) -> Result<(), String> {
+    use tauri_plugin_shell::ShellExt;
+
     info!("Opening external URL: {}", url);
-    
-    match tauri::api::shell::open(&tauri::api::shell::Scope::default(), url, None) {
+
+    match app_handle.shell().open(&url, None) {
         Ok(_) => Ok(()),
         Err(e) => {
             error!("Failed to open URL: {}", e);
@@ -329,6 +459,27 @@ pub async fn open_external_url(url: String) -> Result<(), String> {
     }
 }
 
+// ===== Classification Rule Commands =====
+
+#[tauri::command]
+pub async fn validate_rule_script(script: String, state: State<'_, AppState>) -> Result<String, String> {
+    let engine = state.rule_engine.lock().await;
+    match engine.validate(&script) {
+        Ok(_) => Ok("Script compiled successfully".to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+// ===== Diagnostics Commands =====
+
+#[tauri::command]
+pub async fn get_metrics(state: State<'_, AppState>) -> Result<String, String> {
+    if !state.settings.get().await.enable_workflow_metrics {
+        return Err("Workflow metrics are disabled in settings".to_string());
+    }
+    Ok(state.metrics_handle.render())
+}
+
 #[tauri::command]
 pub async fn show_in_folder(path: String) -> Result<(), String> {
     info!("Showing path in folder: {}", path);
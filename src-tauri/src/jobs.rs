@@ -0,0 +1,287 @@
+// Background job queue for long-running organization runs.
+//
+// `execute_organization` used to block the calling command until the backend
+// finished the whole plan. Instead we hand the plan to a worker task that
+// drives execution, polls the backend for progress, and streams updates to
+// the frontend via Tauri events, while the command itself returns immediately
+// with an `operation_id`.
+
+use log::{debug, error, info, warn};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{Mutex, Notify};
+use tokio::time::{sleep, Duration};
+
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::python_bridge::PythonBridge;
+use crate::rollback;
+use crate::types::{
+    OperationProgress, OrganizeExecuteRequest, OrganizeExecuteResponse, RollbackRequest,
+    RollbackResponse,
+};
+use crate::AppState;
+
+const PROGRESS_EVENT: &str = "organization-progress";
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Shared map of in-flight and recently-finished operations, keyed by
+/// `operation_id`.
+#[derive(Default)]
+pub struct JobQueue {
+    progress: Mutex<HashMap<String, OperationProgress>>,
+    cancellations: Mutex<HashMap<String, Arc<Notify>>>,
+}
+
+impl JobQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn get_progress(&self, operation_id: &str) -> Option<OperationProgress> {
+        self.progress.lock().await.get(operation_id).cloned()
+    }
+
+    async fn set_progress(&self, progress: OperationProgress) {
+        self.progress
+            .lock()
+            .await
+            .insert(progress.operation_id.clone(), progress);
+    }
+
+    async fn register_cancellation(&self, operation_id: &str) -> Arc<Notify> {
+        let notify = Arc::new(Notify::new());
+        self.cancellations
+            .lock()
+            .await
+            .insert(operation_id.to_string(), notify.clone());
+        notify
+    }
+
+    pub async fn cancel(&self, operation_id: &str) -> bool {
+        if let Some(notify) = self.cancellations.lock().await.get(operation_id) {
+            // `notify_one` stores a permit if the worker is mid-poll rather
+            // than waiting, so the cancellation isn't lost.
+            notify.notify_one();
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Spawn a worker that executes `request` against the Python backend and
+/// streams `OperationProgress` updates to the frontend until the plan
+/// finishes, fails, or is cancelled. Returns the backend's initial
+/// `OrganizeExecuteResponse`, including the `operation_id` the worker tracks.
+pub async fn spawn_organization_job(
+    app_handle: AppHandle,
+    request: OrganizeExecuteRequest,
+) -> Result<OrganizeExecuteResponse, String> {
+    let state: tauri::State<AppState> = app_handle.state();
+    let bridge = state.python_bridge.lock().await;
+    let execute_response = bridge
+        .execute_organization(request)
+        .await
+        .map_err(|e| format!("Failed to start organization run: {}", e))?;
+    drop(bridge);
+
+    let operation_id = execute_response.operation_id.clone();
+    let queue = state.job_queue.clone();
+    let python_bridge = state.python_bridge.clone();
+
+    queue
+        .set_progress(OperationProgress {
+            operation_id: operation_id.clone(),
+            current_step: 0,
+            total_steps: 1,
+            current_operation: "Starting".to_string(),
+            files_processed: 0,
+            total_files: 0,
+            bytes_processed: 0,
+            total_bytes: 0,
+            elapsed_time_seconds: 0.0,
+            estimated_remaining_seconds: 0.0,
+            status: "running".to_string(),
+            error_message: None,
+        })
+        .await;
+
+    let cancel_notify = queue.register_cancellation(&operation_id).await;
+
+    let worker_operation_id = operation_id.clone();
+    tokio::spawn(async move {
+        run_worker(
+            app_handle,
+            queue,
+            python_bridge,
+            worker_operation_id,
+            cancel_notify,
+        )
+        .await;
+    });
+
+    Ok(execute_response)
+}
+
+/// Replay a rollback manifest and report progress through the same
+/// `OperationProgress` shape and `organization-progress` event an
+/// organization run uses, so the frontend needs no separate UI plumbing.
+/// `replay_journal` is synchronous filesystem work, so it runs on the
+/// blocking thread pool; its per-file callback feeds a channel that this
+/// function drains to turn each restored file into a real progress update,
+/// rather than yielding a single terminal one.
+pub async fn spawn_rollback_job(
+    app_handle: AppHandle,
+    request: RollbackRequest,
+) -> Result<RollbackResponse, String> {
+    let state: tauri::State<AppState> = app_handle.state();
+    let queue = state.job_queue.clone();
+
+    let entries = rollback::read_journal(&request.manifest_path).map_err(|e| e.to_string())?;
+    let total = entries.len() as u32;
+    let operation_id = entries
+        .first()
+        .map(|entry| entry.operation_id.clone())
+        .unwrap_or_else(|| format!("rollback-{}", request.manifest_path));
+
+    let mut progress = blank_progress(&operation_id);
+    progress.total_steps = total;
+    progress.total_files = total;
+    progress.current_operation = "Rolling back".to_string();
+    queue.set_progress(progress.clone()).await;
+    emit_progress(&app_handle, &progress);
+
+    let manifest_path = request.manifest_path.clone();
+    let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel::<(u32, u32, u64)>();
+
+    let replay_task = tokio::task::spawn_blocking(move || {
+        rollback::replay_journal(&manifest_path, |done, total, bytes_done| {
+            let _ = progress_tx.send((done, total, bytes_done));
+        })
+    });
+
+    while let Some((done, total, bytes_done)) = progress_rx.recv().await {
+        progress.current_step = done;
+        progress.total_steps = total;
+        progress.files_processed = done;
+        progress.bytes_processed = bytes_done;
+        queue.set_progress(progress.clone()).await;
+        emit_progress(&app_handle, &progress);
+    }
+
+    let response = replay_task
+        .await
+        .map_err(|e| format!("Rollback replay task panicked: {}", e))?
+        .map_err(|e| e.to_string())?;
+
+    progress.current_step = total;
+    progress.files_processed = response.files_restored + response.files_skipped;
+    progress.status = response.status.clone();
+    if !response.files_failed.is_empty() {
+        progress.error_message = Some(format!(
+            "{} file(s) could not be rolled back",
+            response.files_failed.len()
+        ));
+    }
+    queue.set_progress(progress.clone()).await;
+    emit_progress(&app_handle, &progress);
+
+    info!(
+        "Rollback of {} finished with status {}",
+        request.manifest_path, response.status
+    );
+
+    Ok(response)
+}
+
+async fn run_worker(
+    app_handle: AppHandle,
+    queue: Arc<JobQueue>,
+    python_bridge: Arc<Mutex<PythonBridge>>,
+    operation_id: String,
+    cancel_notify: Arc<Notify>,
+) {
+    info!("Organization worker started for operation {}", operation_id);
+
+    loop {
+        tokio::select! {
+            _ = cancel_notify.notified() => {
+                warn!("Organization operation {} cancelled", operation_id);
+                let mut progress = queue
+                    .get_progress(&operation_id)
+                    .await
+                    .unwrap_or_else(|| blank_progress(&operation_id));
+                progress.status = "cancelled".to_string();
+                queue.set_progress(progress.clone()).await;
+                emit_progress(&app_handle, &progress);
+                return;
+            }
+            _ = sleep(POLL_INTERVAL) => {}
+        }
+
+        let bridge = python_bridge.lock().await;
+        let polled = bridge
+            .get_request(&format!("/organize/progress/{}", operation_id))
+            .await;
+        drop(bridge);
+
+        let progress = match polled {
+            Ok(value) => match serde_json::from_value::<OperationProgress>(value) {
+                Ok(progress) => progress,
+                Err(e) => {
+                    debug!("Could not parse progress payload: {}", e);
+                    continue;
+                }
+            },
+            Err(e) => {
+                debug!("Progress poll failed for {}: {}", operation_id, e);
+                continue;
+            }
+        };
+
+        let finished = matches!(progress.status.as_str(), "completed" | "failed" | "cancelled");
+        queue.set_progress(progress.clone()).await;
+        emit_progress(&app_handle, &progress);
+
+        if finished {
+            if progress.status == "failed" {
+                error!(
+                    "Organization operation {} failed: {:?}",
+                    operation_id, progress.error_message
+                );
+            } else {
+                info!("Organization operation {} finished: {}", operation_id, progress.status);
+            }
+
+            let state: tauri::State<AppState> = app_handle.state();
+            if state.settings.get().await.enable_workflow_metrics {
+                crate::telemetry::record_organize_progress_terminal(&progress);
+            }
+            return;
+        }
+    }
+}
+
+fn blank_progress(operation_id: &str) -> OperationProgress {
+    OperationProgress {
+        operation_id: operation_id.to_string(),
+        current_step: 0,
+        total_steps: 1,
+        current_operation: "Unknown".to_string(),
+        files_processed: 0,
+        total_files: 0,
+        bytes_processed: 0,
+        total_bytes: 0,
+        elapsed_time_seconds: 0.0,
+        estimated_remaining_seconds: 0.0,
+        status: "unknown".to_string(),
+        error_message: None,
+    }
+}
+
+fn emit_progress(app_handle: &AppHandle, progress: &OperationProgress) {
+    if let Err(e) = app_handle.emit(PROGRESS_EVENT, progress) {
+        error!("Failed to emit organization progress event: {}", e);
+    }
+}
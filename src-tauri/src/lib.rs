@@ -6,6 +6,12 @@
 pub mod python_bridge;
 pub mod process_manager;
 pub mod commands;
+pub mod proxy;
+pub mod jobs;
+pub mod telemetry;
+pub mod rules;
+pub mod settings;
+pub mod rollback;
 pub mod types;
 
 #[cfg(test)]
@@ -4,44 +4,84 @@
 mod python_bridge;
 mod process_manager;
 mod commands;
+mod proxy;
+mod jobs;
+mod telemetry;
+mod rules;
+mod settings;
+mod rollback;
 
-use log::{info, error};
+use tracing::{info, error};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tauri::{Manager, State};
 
 use python_bridge::PythonBridge;
 use process_manager::ProcessManager;
+use jobs::JobQueue;
+use rules::RuleEngine;
+use settings::SettingsManager;
+use types::BackendMode;
 use commands::*;
 
 // Application state
 pub struct AppState {
     pub python_bridge: Arc<Mutex<PythonBridge>>,
     pub process_manager: Arc<Mutex<ProcessManager>>,
+    pub job_queue: Arc<JobQueue>,
+    pub metrics_handle: metrics_exporter_prometheus::PrometheusHandle,
+    pub rule_engine: Arc<Mutex<RuleEngine>>,
+    pub settings: Arc<SettingsManager>,
 }
 
 fn main() {
-    // Initialize logging
-    env_logger::init();
+    // Initialize structured logging and the Prometheus recorder
+    telemetry::init_tracing();
+    let metrics_handle = telemetry::init_metrics();
     info!("Starting DevSteward AI desktop application");
 
     // Initialize application state
     let python_bridge = Arc::new(Mutex::new(PythonBridge::new()));
     let process_manager = Arc::new(Mutex::new(ProcessManager::new()));
-    
+    let job_queue = Arc::new(JobQueue::new());
+
+    let mut rule_engine = RuleEngine::new();
+    if let Err(e) = rule_engine.load_from_config_dir() {
+        error!("Failed to load classification rules: {}", e);
+    }
+    let rule_engine = Arc::new(Mutex::new(rule_engine));
+
+    let settings = Arc::new(
+        SettingsManager::load().expect("Failed to load or initialize application settings"),
+    );
+
     let app_state = AppState {
         python_bridge,
         process_manager,
+        job_queue,
+        metrics_handle,
+        rule_engine,
+        settings: settings.clone(),
     };
 
     tauri::Builder::default()
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_shell::init())
         .manage(app_state)
+        .register_asynchronous_uri_scheme_protocol("steward", |app, request, responder| {
+            let app_handle = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let response = proxy::handle_request(&app_handle, request).await;
+                responder.respond(response);
+            });
+        })
         .invoke_handler(tauri::generate_handler![
             // System commands
             check_health,
             start_python_backend,
             stop_python_backend,
             get_backend_status,
+            get_backend_logs,
             
             // Project analysis commands
             scan_project_directory,
@@ -51,6 +91,8 @@ fn main() {
             preview_organization,
             execute_organization,
             get_organization_progress,
+            cancel_organization,
+            rollback_organization,
             
             // File system commands
             select_directory,
@@ -62,25 +104,78 @@ fn main() {
             
             // Project creation commands (placeholder)
             create_project_from_template,
-            get_project_templates
+            get_project_templates,
+
+            // Diagnostics commands
+            get_metrics,
+
+            // Classification rule commands
+            validate_rule_script
         ])
         .setup(|app| {
-            let app_handle = app.handle();
-            
+            let app_handle = app.handle().clone();
+
+            // Hot-reload settings when the config file changes on disk. The
+            // watcher must outlive `setup`, so it's managed as app state.
+            {
+                let state: State<AppState> = app_handle.state();
+                match settings::watch_for_changes(state.settings.clone(), app_handle.clone()) {
+                    Ok(watcher) => {
+                        app.manage(watcher);
+                    }
+                    Err(e) => error!("Failed to start settings file watcher: {}", e),
+                }
+            }
+
             // Start the Python backend process in the background
             tauri::async_runtime::spawn(async move {
                 let state: State<AppState> = app_handle.state();
+                let settings = state.settings.get().await;
+
+                {
+                    let mut bridge = state.python_bridge.lock().await;
+                    if settings.backend_mode == BackendMode::Remote {
+                        bridge.set_base_url(settings.remote_backend_url.clone());
+                    }
+                    settings::apply_to_bridge(&settings, &mut bridge);
+                }
+
+                let backend_mode = settings.backend_mode.clone();
                 let mut process_manager = state.process_manager.lock().await;
-                
+                process_manager.configure_backend(settings.backend_mode, settings.remote_backend_url);
+
                 match process_manager.start_python_backend().await {
-                    Ok(_) => info!("Python backend started successfully"),
+                    Ok(_) => {
+                        // Local mode may have picked a different port than
+                        // configured if the default was already taken.
+                        let local_port = (backend_mode == BackendMode::Local)
+                            .then(|| process_manager.get_port());
+                        drop(process_manager);
+
+                        let mut bridge = state.python_bridge.lock().await;
+                        if let Some(port) = local_port {
+                            bridge.set_base_url(format!("http://127.0.0.1:{}", port));
+                        }
+                        match bridge.wait_for_ready(std::time::Duration::from_secs(30)).await {
+                            Ok(_) => info!("Python backend started successfully"),
+                            Err(e) => error!("Python backend spawned but never became ready: {}", e),
+                        }
+                    }
                     Err(e) => error!("Failed to start Python backend: {}", e),
                 }
             });
-            
+
+            // Watch for crashes and auto-restart the backend with backoff
+            let supervisor_handle = app.handle().clone();
+            let supervisor_state: State<AppState> = supervisor_handle.state();
+            process_manager::spawn_supervisor(
+                supervisor_state.process_manager.clone(),
+                supervisor_handle.clone(),
+            );
+
             Ok(())
         })
-        .on_window_event(|event| match event.event() {
+        .on_window_event(|_window, event| match event {
             tauri::WindowEvent::CloseRequested { .. } => {
                 info!("Application closing, cleaning up...");
                 // Cleanup will be handled by the Drop implementation
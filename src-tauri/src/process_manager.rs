@@ -1,10 +1,35 @@
-use log::{info, warn, error, debug};
-use std::process::{Child, Command, Stdio};
+use tracing::{info, warn, error, debug};
+use std::collections::VecDeque;
+use std::process::Stdio;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use thiserror::Error;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::{Child, Command};
+use tokio::sync::Mutex;
 use tokio::time::sleep;
 
-use crate::types::ProcessStatus;
+use tauri::{AppHandle, Manager};
+
+use crate::types::{BackendMode, ProcessStatus};
+use crate::AppState;
+
+/// How many trailing lines of backend stdout/stderr to keep for display and
+/// for attaching to error responses.
+const LOG_BUFFER_CAPACITY: usize = 500;
+
+const SUPERVISOR_POLL_INTERVAL: Duration = Duration::from_secs(2);
+const SUPERVISOR_BASE_BACKOFF: Duration = Duration::from_secs(1);
+const SUPERVISOR_MAX_BACKOFF: Duration = Duration::from_secs(60);
+const SUPERVISOR_MAX_RESTARTS: u32 = 5;
+
+/// How long to wait after a graceful SIGTERM before escalating to SIGKILL.
+const GRACEFUL_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+const GRACEFUL_SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How far past the configured port to scan for a free one if it's taken
+/// (e.g. 8008 occupied scans up through 8040).
+const PORT_SCAN_RANGE: u16 = 32;
 
 #[derive(Error, Debug)]
 pub enum ProcessError {
@@ -22,6 +47,24 @@ pub struct ProcessManager {
     python_process: Option<Child>,
     start_time: Option<Instant>,
     port: u16,
+    /// Set once the process has been deliberately stopped (`stop_python_backend`
+    /// or app shutdown), so the supervisor doesn't try to resurrect it.
+    intentionally_stopped: bool,
+    /// True from a successful `start_python_backend` until the next deliberate
+    /// `stop_python_backend`, independent of `start_time`. `is_backend_running`
+    /// clears `start_time` the moment it notices the child has exited, so the
+    /// supervisor needs this separate flag to tell "crashed, please restart"
+    /// apart from "never started" — both would otherwise look like
+    /// `start_time.is_none()`.
+    expected_running: bool,
+    restart_count: u32,
+    /// Set once the supervisor gives up auto-restarting after
+    /// `SUPERVISOR_MAX_RESTARTS` consecutive restarts have each crashed again. Cleared on the next
+    /// deliberate or successful start.
+    terminal_failure_reason: Option<String>,
+    log_buffer: Arc<Mutex<VecDeque<String>>>,
+    mode: BackendMode,
+    remote_url: String,
 }
 
 impl ProcessManager {
@@ -30,19 +73,50 @@ impl ProcessManager {
             python_process: None,
             start_time: None,
             port: 8008,
+            intentionally_stopped: false,
+            expected_running: false,
+            restart_count: 0,
+            terminal_failure_reason: None,
+            log_buffer: Arc::new(Mutex::new(VecDeque::with_capacity(LOG_BUFFER_CAPACITY))),
+            mode: BackendMode::Local,
+            remote_url: String::new(),
         }
     }
 
+    /// Configure whether this manager spawns and owns a local backend process
+    /// or attaches to an already-running one at `remote_url`. Called with the
+    /// current `AppSettings` before the next `start_python_backend`.
+    pub fn configure_backend(&mut self, mode: BackendMode, remote_url: String) {
+        self.mode = mode;
+        self.remote_url = remote_url;
+    }
+
     /// Start the Python FastAPI backend process
     pub async fn start_python_backend(&mut self) -> Result<(), ProcessError> {
+        if self.mode == BackendMode::Remote {
+            info!("Backend mode is Remote, attaching to {}", self.remote_url);
+            self.intentionally_stopped = false;
+            self.expected_running = true;
+            self.terminal_failure_reason = None;
+            self.start_time = Some(Instant::now());
+            self.wait_for_backend_ready().await?;
+            info!("Remote Python backend is ready and accepting connections");
+            return Ok(());
+        }
+
         info!("Starting Python backend process...");
 
         // Kill existing process if running
-        if self.is_backend_running() {
+        if self.is_backend_running().await {
             warn!("Backend already running, stopping first");
             self.stop_python_backend().await?;
         }
 
+        // The configured port may already be in use by a stale instance or
+        // an unrelated service; fall back to the next free port in range
+        // rather than silently failing to become ready.
+        self.port = self.find_available_port()?;
+
         // Find the Python executable path
         let python_path = self.find_python_executable()?;
         let backend_path = self.get_backend_path()?;
@@ -59,11 +133,19 @@ impl ProcessManager {
             .spawn()
             .map_err(|e| ProcessError::StartFailed(format!("Failed to spawn process: {}", e)))?;
 
-        info!("Python backend started with PID: {}", child.id());
+        info!("Python backend started with PID: {}", child.id().unwrap_or(0));
+
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+        self.log_buffer.lock().await.clear();
+        spawn_log_drain(stdout, stderr, self.log_buffer.clone());
 
         // Store the process
         self.python_process = Some(child);
         self.start_time = Some(Instant::now());
+        self.intentionally_stopped = false;
+        self.expected_running = true;
+        self.terminal_failure_reason = None;
 
         // Wait for the backend to be ready
         self.wait_for_backend_ready().await?;
@@ -72,24 +154,67 @@ impl ProcessManager {
         Ok(())
     }
 
-    /// Stop the Python backend process
+    /// Stop the Python backend process. On Unix, sends SIGTERM and gives the process
+    /// `GRACEFUL_SHUTDOWN_TIMEOUT` to exit on its own before escalating to
+    /// SIGKILL, so an in-flight organize run gets a chance to finish its
+    /// current file move instead of being killed mid-write. On other
+    /// platforms the process is killed straight away.
     pub async fn stop_python_backend(&mut self) -> Result<(), ProcessError> {
-        if let Some(mut child) = self.python_process.take() {
-            info!("Stopping Python backend process (PID: {})", child.id());
+        self.intentionally_stopped = true;
+        self.expected_running = false;
 
-            // Try graceful shutdown first
-            match child.kill() {
-                Ok(_) => {
-                    // Wait for process to exit
-                    match child.wait() {
-                        Ok(status) => {
-                            info!("Python backend stopped with status: {}", status);
+        if self.mode == BackendMode::Remote {
+            info!("Backend mode is Remote, detaching without stopping the remote process");
+            self.start_time = None;
+            return Ok(());
+        }
+
+        if let Some(mut child) = self.python_process.take() {
+            let pid = child.id();
+            info!("Stopping Python backend process (PID: {:?})", pid);
+
+            #[cfg(unix)]
+            {
+                // `id()` returns `None` if the child has already been reaped;
+                // `libc::kill(0, _)` signals the whole process group (this app
+                // included), so only signal when we have a real pid and
+                // otherwise fall straight through to the try_wait/kill below.
+                if let Some(pid) = pid {
+                    send_sigterm(pid);
+                }
+                let deadline = Instant::now() + GRACEFUL_SHUTDOWN_TIMEOUT;
+
+                loop {
+                    match child.try_wait() {
+                        Ok(Some(status)) => {
+                            info!("Python backend exited gracefully with status: {}", status);
+                            self.start_time = None;
+                            return Ok(());
+                        }
+                        Ok(None) => {
+                            if Instant::now() >= deadline {
+                                warn!(
+                                    "Python backend did not exit within {:?} of SIGTERM, sending SIGKILL",
+                                    GRACEFUL_SHUTDOWN_TIMEOUT
+                                );
+                                break;
+                            }
+                            sleep(GRACEFUL_SHUTDOWN_POLL_INTERVAL).await;
                         }
                         Err(e) => {
-                            warn!("Error waiting for process to exit: {}", e);
+                            warn!("Error polling process during graceful shutdown: {}", e);
+                            break;
                         }
                     }
                 }
+            }
+
+            // Either we're on a platform without POSIX signals, or the
+            // process ignored SIGTERM within the grace period.
+            match child.kill().await {
+                Ok(_) => {
+                    info!("Python backend force-killed");
+                }
                 Err(e) => {
                     error!("Failed to kill Python backend process: {}", e);
                     return Err(ProcessError::CommunicationFailed(format!("Kill failed: {}", e)));
@@ -104,8 +229,13 @@ impl ProcessManager {
         Ok(())
     }
 
-    /// Check if the backend process is running
-    pub fn is_backend_running(&mut self) -> bool {
+    /// Check if the backend process is running. In `Remote` mode there's no
+    /// child process to poll, so this is determined purely by a health check.
+    pub async fn is_backend_running(&mut self) -> bool {
+        if self.mode == BackendMode::Remote {
+            return matches!(self.check_backend_health().await, Ok(true));
+        }
+
         if let Some(ref mut child) = self.python_process {
             match child.try_wait() {
                 Ok(Some(_)) => {
@@ -130,9 +260,12 @@ impl ProcessManager {
     }
 
     /// Get detailed status of the backend process
-    pub fn get_backend_status(&mut self) -> ProcessStatus {
-        let is_running = self.is_backend_running();
-        let pid = self.python_process.as_ref().map(|child| child.id());
+    pub async fn get_backend_status(&mut self) -> ProcessStatus {
+        let is_running = self.is_backend_running().await;
+        let pid = match self.mode {
+            BackendMode::Remote => None,
+            BackendMode::Local => self.python_process.as_ref().and_then(|child| child.id()),
+        };
         let uptime_seconds = self.start_time.map(|start| start.elapsed().as_secs());
 
         ProcessStatus {
@@ -141,6 +274,8 @@ impl ProcessManager {
             port: self.port,
             uptime_seconds,
             health_status: None, // Will be filled by health check
+            restart_count: self.restart_count,
+            terminal_failure_reason: self.terminal_failure_reason.clone(),
         }
     }
 
@@ -176,7 +311,10 @@ impl ProcessManager {
     /// Check if the backend is healthy by calling the health endpoint
     async fn check_backend_health(&self) -> Result<bool, ProcessError> {
         let client = reqwest::Client::new();
-        let url = format!("http://127.0.0.1:{}/health", self.port);
+        let url = match self.mode {
+            BackendMode::Local => format!("http://127.0.0.1:{}/health", self.port),
+            BackendMode::Remote => format!("{}/health", self.remote_url.trim_end_matches('/')),
+        };
 
         match client.get(&url).timeout(Duration::from_secs(5)).send().await {
             Ok(response) => {
@@ -194,6 +332,28 @@ impl ProcessManager {
         }
     }
 
+    /// Return `self.port` if it's free, otherwise scan up to
+    /// `PORT_SCAN_RANGE` ports above it and return the first free one.
+    pub(crate) fn find_available_port(&self) -> Result<u16, ProcessError> {
+        if is_port_available(self.port) {
+            return Ok(self.port);
+        }
+
+        warn!("Port {} is already in use, scanning for a free port", self.port);
+        for candidate in self.port..=self.port.saturating_add(PORT_SCAN_RANGE) {
+            if is_port_available(candidate) {
+                info!("Using port {} instead", candidate);
+                return Ok(candidate);
+            }
+        }
+
+        Err(ProcessError::StartFailed(format!(
+            "No free port found in range {}-{}",
+            self.port,
+            self.port.saturating_add(PORT_SCAN_RANGE)
+        )))
+    }
+
     /// Find the Python executable (preferring virtual environment)
     fn find_python_executable(&self) -> Result<String, ProcessError> {
         let backend_path = self.get_backend_path()?;
@@ -242,17 +402,193 @@ impl ProcessManager {
     pub fn get_port(&self) -> u16 {
         self.port
     }
+
+    /// Return up to the last `n` lines of drained backend stdout/stderr, most
+    /// recent last. Exposed to the frontend through the `get_backend_logs`
+    /// command, e.g. to show backend output during `wait_for_ready`.
+    pub async fn recent_backend_logs(&self, n: usize) -> Vec<String> {
+        let buffer = self.log_buffer.lock().await;
+        buffer.iter().rev().take(n).rev().cloned().collect()
+    }
+
+    /// Put this manager into the state `is_backend_running` leaves it in the
+    /// instant it notices a crashed child: `start_time` cleared, but still
+    /// `expected_running` since nothing deliberately stopped it. Lets tests
+    /// exercise the supervisor's restart-eligibility check without spawning
+    /// and killing a real child process.
+    #[cfg(test)]
+    pub(crate) fn simulate_started_then_crashed_for_test(&mut self) {
+        self.expected_running = true;
+        self.start_time = None;
+    }
+
+    #[cfg(test)]
+    pub(crate) fn is_restart_eligible_for_test(&self) -> bool {
+        self.expected_running
+    }
+}
+
+/// Spawn two tasks that drain the child's stdout/stderr line-by-line,
+/// re-emitting each line through `tracing` and pushing it into the shared
+/// ring buffer. Unread pipes would otherwise fill uvicorn's stdout/stderr
+/// buffer and deadlock it once full.
+fn spawn_log_drain(
+    stdout: Option<tokio::process::ChildStdout>,
+    stderr: Option<tokio::process::ChildStderr>,
+    log_buffer: Arc<Mutex<VecDeque<String>>>,
+) {
+    if let Some(stdout) = stdout {
+        let log_buffer = log_buffer.clone();
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stdout).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                debug!("[backend stdout] {}", line);
+                push_log_line(&log_buffer, line).await;
+            }
+        });
+    }
+
+    if let Some(stderr) = stderr {
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                warn!("[backend stderr] {}", line);
+                push_log_line(&log_buffer, line).await;
+            }
+        });
+    }
+}
+
+async fn push_log_line(log_buffer: &Arc<Mutex<VecDeque<String>>>, line: String) {
+    let mut buffer = log_buffer.lock().await;
+    if buffer.len() >= LOG_BUFFER_CAPACITY {
+        buffer.pop_front();
+    }
+    buffer.push_back(line);
+}
+
+/// Whether `port` can be bound on localhost right now. Best-effort: the port
+/// could be taken between this check and the actual `uvicorn` bind, but that
+/// race is no worse than the one a failed `wait_for_backend_ready` already
+/// tolerates.
+fn is_port_available(port: u16) -> bool {
+    std::net::TcpListener::bind(("127.0.0.1", port)).is_ok()
+}
+
+/// Spawn a supervisor task that watches the backend process and restarts it
+/// with exponential backoff if it crashes. Stops retrying (and leaves the
+/// backend down) once `SUPERVISOR_MAX_RESTARTS` consecutive restarts have each
+/// crashed again, and
+/// resets the restart count once the backend has been running again for a
+/// while.
+pub fn spawn_supervisor(process_manager: Arc<Mutex<ProcessManager>>, app_handle: AppHandle) {
+    tokio::spawn(async move {
+        loop {
+            sleep(SUPERVISOR_POLL_INTERVAL).await;
+
+            let mut manager = process_manager.lock().await;
+
+            if manager.intentionally_stopped {
+                continue;
+            }
+
+            if manager.is_backend_running().await {
+                // A reasonable uptime resets the crash counter so a single
+                // flaky restart doesn't count against a later, unrelated one.
+                if manager.restart_count > 0
+                    && manager.start_time.map(|s| s.elapsed() > Duration::from_secs(60)).unwrap_or(false)
+                {
+                    manager.restart_count = 0;
+                }
+                continue;
+            }
+
+            if !manager.expected_running {
+                // Never started yet (or deliberately stopped); nothing crashed.
+                continue;
+            }
+
+            if manager.restart_count >= SUPERVISOR_MAX_RESTARTS {
+                let reason = format!(
+                    "Backend crashed again after {} automatic restarts in a row; auto-restart gave up",
+                    manager.restart_count
+                );
+                error!("{}", reason);
+                manager.terminal_failure_reason = Some(reason);
+                manager.expected_running = false;
+                manager.start_time = None;
+                continue;
+            }
+
+            let backoff = SUPERVISOR_BASE_BACKOFF
+                .checked_mul(2u32.saturating_pow(manager.restart_count))
+                .unwrap_or(SUPERVISOR_MAX_BACKOFF)
+                .min(SUPERVISOR_MAX_BACKOFF);
+
+            warn!(
+                "Python backend appears to have crashed, restarting in {:?} (attempt {}/{})",
+                backoff,
+                manager.restart_count + 1,
+                SUPERVISOR_MAX_RESTARTS
+            );
+
+            drop(manager);
+            sleep(backoff).await;
+            let mut manager = process_manager.lock().await;
+
+            if manager.intentionally_stopped {
+                continue;
+            }
+
+            manager.restart_count += 1;
+            if let Err(e) = manager.start_python_backend().await {
+                error!("Supervisor failed to restart Python backend: {}", e);
+            } else {
+                info!("Supervisor successfully restarted Python backend");
+                let state: tauri::State<AppState> = app_handle.state();
+                if state.settings.get().await.enable_workflow_metrics {
+                    crate::telemetry::record_backend_restart();
+                }
+            }
+        }
+    });
+}
+
+#[cfg(unix)]
+fn send_sigterm(pid: u32) {
+    unsafe {
+        libc::kill(pid as libc::pid_t, libc::SIGTERM);
+    }
 }
 
 impl Drop for ProcessManager {
     fn drop(&mut self) {
-        if self.python_process.is_some() {
-            warn!("ProcessManager dropping with active process, attempting cleanup");
-            // Note: We can't use async here, so we do a synchronous kill
-            if let Some(mut child) = self.python_process.take() {
-                let _ = child.kill();
-                let _ = child.wait();
+        if let Some(mut child) = self.python_process.take() {
+            warn!("ProcessManager dropping with active process, attempting graceful cleanup");
+
+            // Note: we can't use async here, so the grace-period wait below
+            // is a synchronous poll rather than `stop_python_backend`'s one.
+            #[cfg(unix)]
+            {
+                if let Some(pid) = child.id() {
+                    send_sigterm(pid);
+                }
+                let deadline = Instant::now() + GRACEFUL_SHUTDOWN_TIMEOUT;
+
+                loop {
+                    match child.try_wait() {
+                        Ok(Some(_)) => return,
+                        Ok(None) if Instant::now() < deadline => {
+                            std::thread::sleep(GRACEFUL_SHUTDOWN_POLL_INTERVAL);
+                        }
+                        _ => break,
+                    }
+                }
             }
+
+            // `Child::kill` is async in tokio; `start_kill` is the
+            // fire-and-forget sync equivalent available from `Drop`.
+            let _ = child.start_kill();
         }
     }
 }
\ No newline at end of file
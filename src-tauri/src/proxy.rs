@@ -0,0 +1,75 @@
+// Asynchronous URI-scheme protocol handler that proxies webview requests to the
+// Python backend through `PythonBridge`, so the webview never has to call the
+// backend's loopback port itself. The backend still listens on 127.0.0.1.
+
+use log::{debug, error, warn};
+use serde_json::Value;
+use tauri::http::{Method, Request, Response};
+use tauri::{AppHandle, Manager, State};
+
+use crate::AppState;
+
+/// Handle a single `steward://` request by forwarding it to the Python backend
+/// and rebuilding the webview response from the backend's reply.
+pub async fn handle_request(app_handle: &AppHandle, request: Request<Vec<u8>>) -> Response<Vec<u8>> {
+    let (parts, body) = request.into_parts();
+    // `path()` alone would silently drop `?query=strings`, breaking any
+    // backend endpoint that takes query parameters (e.g. a paginated list).
+    let path_and_query = parts
+        .uri
+        .path_and_query()
+        .map(|pq| pq.as_str())
+        .unwrap_or_else(|| parts.uri.path());
+
+    debug!("Proxying {} {} through steward:// scheme", parts.method, path_and_query);
+
+    let state: State<AppState> = app_handle.state();
+    let bridge = state.python_bridge.lock().await;
+
+    let result = match parts.method {
+        Method::GET => bridge.get_request_with_status(path_and_query).await,
+        Method::POST => match parse_body(&body) {
+            Ok(json_body) => bridge.post_request_with_status(path_and_query, &json_body).await,
+            Err(e) => return error_response(400, &e),
+        },
+        other => return error_response(405, &format!("Unsupported method: {}", other)),
+    };
+
+    match result {
+        // Forward the backend's own status code so the webview can tell a
+        // 404 from a 500 instead of seeing every outcome as one flat 502.
+        Ok((status, value)) => json_response(status, &value),
+        Err(e) => {
+            warn!("Proxied request to {} failed: {}", path_and_query, e);
+            error_response(502, &e.to_string())
+        }
+    }
+}
+
+fn parse_body(body: &[u8]) -> Result<Value, String> {
+    if body.is_empty() {
+        return Ok(Value::Null);
+    }
+    serde_json::from_slice(body).map_err(|e| format!("Invalid request body: {}", e))
+}
+
+fn json_response(status: u16, value: &Value) -> Response<Vec<u8>> {
+    let bytes = serde_json::to_vec(value).unwrap_or_default();
+    build_response(status, bytes)
+}
+
+fn error_response(status: u16, message: &str) -> Response<Vec<u8>> {
+    let body = serde_json::json!({ "error": message });
+    json_response(status, &body)
+}
+
+fn build_response(status: u16, body: Vec<u8>) -> Response<Vec<u8>> {
+    Response::builder()
+        .status(status)
+        .header("Content-Type", "application/json")
+        .body(body)
+        .unwrap_or_else(|e| {
+            error!("Failed to build proxy response: {}", e);
+            Response::new(Vec::new())
+        })
+}
@@ -1,16 +1,25 @@
-use log::{info, warn, error, debug};
+use tracing::{info, warn, error, debug, instrument};
 use reqwest::Client;
+use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
+use reqwest_retry::policies::ExponentialBackoff;
+use reqwest_retry::RetryTransientMiddleware;
 use serde_json::Value;
 use std::collections::HashMap;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
 use crate::types::*;
 
+const DEFAULT_BASE_RETRY_DELAY: Duration = Duration::from_millis(200);
+const DEFAULT_MAX_RETRY_DELAY: Duration = Duration::from_secs(5);
+const DEFAULT_MAX_RETRIES: u32 = 5;
+
 #[derive(Error, Debug)]
 pub enum BridgeError {
     #[error("HTTP request failed: {0}")]
     RequestFailed(#[from] reqwest::Error),
+    #[error("HTTP request failed after retries: {0}")]
+    RequestFailedWithRetries(#[from] reqwest_middleware::Error),
     #[error("JSON parsing failed: {0}")]
     JsonError(#[from] serde_json::Error),
     #[error("Backend not available")]
@@ -22,20 +31,31 @@ pub enum BridgeError {
 }
 
 pub struct PythonBridge {
-    client: Client,
+    /// Retries idempotent GETs and connection errors with backoff.
+    client: ClientWithMiddleware,
+    /// No retry middleware: used for POSTs like `/organize/execute` where
+    /// re-issuing a timed-out request could replay a plan that already
+    /// started moving files.
+    write_client: Client,
     base_url: String,
+    base_retry_delay: Duration,
+    max_retry_delay: Duration,
+    max_retries: u32,
 }
 
 impl PythonBridge {
     pub fn new() -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(30))
-            .build()
-            .expect("Failed to create HTTP client");
+        let base_retry_delay = DEFAULT_BASE_RETRY_DELAY;
+        let max_retry_delay = DEFAULT_MAX_RETRY_DELAY;
+        let max_retries = DEFAULT_MAX_RETRIES;
 
         Self {
-            client,
+            client: build_client(base_retry_delay, max_retry_delay, max_retries),
+            write_client: build_write_client(),
             base_url: "http://127.0.0.1:8008".to_string(),
+            base_retry_delay,
+            max_retry_delay,
+            max_retries,
         }
     }
 
@@ -43,6 +63,52 @@ impl PythonBridge {
         self.base_url = url;
     }
 
+    /// Set the base delay used for the first retry's exponential backoff.
+    pub fn set_base_retry_delay(&mut self, delay: Duration) {
+        self.base_retry_delay = delay;
+        self.rebuild_client();
+    }
+
+    /// Set the cap applied to each backoff delay, regardless of attempt count.
+    pub fn set_max_retry_delay(&mut self, delay: Duration) {
+        self.max_retry_delay = delay;
+        self.rebuild_client();
+    }
+
+    /// Set how many times a transient failure (connection error or idempotent
+    /// GET) is retried before giving up.
+    pub fn set_max_retries(&mut self, max_retries: u32) {
+        self.max_retries = max_retries;
+        self.rebuild_client();
+    }
+
+    fn rebuild_client(&mut self) {
+        self.client = build_client(self.base_retry_delay, self.max_retry_delay, self.max_retries);
+    }
+
+    /// Poll `/health` with exponential backoff until the backend answers or
+    /// `timeout` elapses. Called after spawning the backend so `start_python_backend`
+    /// only reports success once requests will actually succeed.
+    pub async fn wait_for_ready(&self, timeout: Duration) -> Result<HealthResponse, BridgeError> {
+        let deadline = Instant::now() + timeout;
+        let mut delay = self.base_retry_delay;
+
+        loop {
+            match self.check_health().await {
+                Ok(health) => return Ok(health),
+                Err(e) => {
+                    if Instant::now() >= deadline {
+                        warn!("Backend did not become ready within {:?}", timeout);
+                        return Err(e);
+                    }
+                    debug!("Backend not ready yet ({}), retrying in {:?}", e, delay);
+                    tokio::time::sleep(delay.min(self.max_retry_delay)).await;
+                    delay = (delay * 2).min(self.max_retry_delay);
+                }
+            }
+        }
+    }
+
     /// Check backend health
     pub async fn check_health(&self) -> Result<HealthResponse, BridgeError> {
         debug!("Checking backend health");
@@ -81,17 +147,19 @@ impl PythonBridge {
     }
 
     /// Scan a project directory
+    #[instrument(skip(self, request), fields(path = %request.path))]
     pub async fn scan_project_directory(&self, request: ScanRequest) -> Result<ScanResponse, BridgeError> {
         info!("Scanning project directory: {}", request.path);
         let url = format!("{}/scan", self.base_url);
-        
-        let response = self.client
+        let started_at = Instant::now();
+
+        let response = self.write_client
             .post(&url)
             .json(&request)
             .send()
             .await?;
 
-        if response.status().is_success() {
+        let result = if response.status().is_success() {
             let scan_result: ScanResponse = response.json().await?;
             info!("Scan completed successfully: {}", scan_result.scan_id);
             Ok(scan_result)
@@ -99,21 +167,26 @@ impl PythonBridge {
             let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
             error!("Scan failed: {}", error_text);
             Err(BridgeError::BackendError(error_text))
-        }
+        };
+
+        record_call("scan", started_at.elapsed(), result.as_ref().err());
+        result
     }
 
     /// Preview organization plan
+    #[instrument(skip(self, request), fields(scan_id = %request.scan_id))]
     pub async fn preview_organization(&self, request: OrganizePreviewRequest) -> Result<OrganizePreviewResponse, BridgeError> {
         info!("Generating organization preview for scan: {}", request.scan_id);
         let url = format!("{}/organize/preview", self.base_url);
-        
-        let response = self.client
+        let started_at = Instant::now();
+
+        let response = self.write_client
             .post(&url)
             .json(&request)
             .send()
             .await?;
 
-        if response.status().is_success() {
+        let result = if response.status().is_success() {
             let preview: OrganizePreviewResponse = response.json().await?;
             info!("Organization preview generated: {} operations", preview.total_operations);
             Ok(preview)
@@ -121,21 +194,29 @@ impl PythonBridge {
             let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
             error!("Organization preview failed: {}", error_text);
             Err(BridgeError::BackendError(error_text))
-        }
+        };
+
+        record_call("organize_preview", started_at.elapsed(), result.as_ref().err());
+        result
     }
 
     /// Execute organization plan
+    #[instrument(skip(self, request), fields(plan_id = %request.plan_id))]
     pub async fn execute_organization(&self, request: OrganizeExecuteRequest) -> Result<OrganizeExecuteResponse, BridgeError> {
         info!("Executing organization plan: {}", request.plan_id);
         let url = format!("{}/organize/execute", self.base_url);
-        
-        let response = self.client
+        let started_at = Instant::now();
+
+        // Not retried: a transient failure after the backend has already
+        // begun moving files must not be masked by silently re-issuing this
+        // POST, which would risk running the plan twice.
+        let response = self.write_client
             .post(&url)
             .json(&request)
             .send()
             .await?;
 
-        if response.status().is_success() {
+        let result = if response.status().is_success() {
             let execute_result: OrganizeExecuteResponse = response.json().await?;
             info!("Organization execution status: {}", execute_result.status);
             Ok(execute_result)
@@ -143,15 +224,18 @@ impl PythonBridge {
             let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
             error!("Organization execution failed: {}", error_text);
             Err(BridgeError::BackendError(error_text))
-        }
+        };
+
+        record_call("organize_execute", started_at.elapsed(), result.as_ref().err());
+        result
     }
 
     /// Create a new project from template
     pub async fn create_project_from_template(&self, request: CreateProjectRequest) -> Result<Value, BridgeError> {
         info!("Creating project from template: {}", request.template_id);
         let url = format!("{}/projects/create", self.base_url);
-        
-        let response = self.client
+
+        let response = self.write_client
             .post(&url)
             .json(&request)
             .send()
@@ -217,12 +301,13 @@ impl PythonBridge {
         }
     }
 
-    /// Make a generic POST request to the backend
+    /// Make a generic POST request to the backend. Not retried: the endpoint
+    /// may not be idempotent, and this helper has no way to know.
     pub async fn post_request(&self, endpoint: &str, data: &Value) -> Result<Value, BridgeError> {
         let url = format!("{}{}", self.base_url, endpoint);
         debug!("Making POST request to: {}", url);
-        
-        let response = self.client
+
+        let response = self.write_client
             .post(&url)
             .json(data)
             .send()
@@ -237,6 +322,28 @@ impl PythonBridge {
         }
     }
 
+    /// Make a generic GET request to the backend, returning the backend's
+    /// real status code alongside the body instead of collapsing every
+    /// non-2xx into a single error variant. Used by the `steward://` proxy,
+    /// which needs to pass that status straight through to the webview.
+    pub async fn get_request_with_status(&self, path_and_query: &str) -> Result<(u16, Value), BridgeError> {
+        let url = format!("{}{}", self.base_url, path_and_query);
+        debug!("Making GET request to: {}", url);
+
+        let response = self.client.get(&url).send().await?;
+        parse_proxied_response(response).await
+    }
+
+    /// Make a generic POST request to the backend, returning the backend's
+    /// real status code alongside the body. See `get_request_with_status`.
+    pub async fn post_request_with_status(&self, path_and_query: &str, data: &Value) -> Result<(u16, Value), BridgeError> {
+        let url = format!("{}{}", self.base_url, path_and_query);
+        debug!("Making POST request to: {}", url);
+
+        let response = self.write_client.post(&url).json(data).send().await?;
+        parse_proxied_response(response).await
+    }
+
     /// Test connectivity to the backend
     pub async fn test_connectivity(&self) -> bool {
         match self.check_health().await {
@@ -255,4 +362,75 @@ impl PythonBridge {
     pub fn get_base_url(&self) -> &str {
         &self.base_url
     }
+}
+
+/// Read a proxied response's status and body without treating a non-2xx
+/// status as an error: the caller (the `steward://` proxy) needs the
+/// backend's real status code, not `BridgeError`'s collapsed view of it.
+async fn parse_proxied_response(response: reqwest::Response) -> Result<(u16, Value), BridgeError> {
+    let status = response.status().as_u16();
+    let text = response.text().await?;
+    let value = if text.is_empty() {
+        Value::Null
+    } else {
+        serde_json::from_str(&text).unwrap_or_else(|_| serde_json::json!({ "error": text }))
+    };
+    Ok((status, value))
+}
+
+/// Record a bridge call's outcome as Prometheus counters/histogram, labeled by
+/// endpoint and (on failure) by `BridgeError` variant.
+fn record_call(endpoint: &'static str, elapsed: Duration, error: Option<&BridgeError>) {
+    metrics::counter!("bridge_requests_total", "endpoint" => endpoint).increment(1);
+    metrics::histogram!("bridge_request_duration_seconds", "endpoint" => endpoint)
+        .record(elapsed.as_secs_f64());
+
+    if let Some(e) = error {
+        metrics::counter!(
+            "bridge_request_errors_total",
+            "endpoint" => endpoint,
+            "error" => error_variant(e),
+        )
+        .increment(1);
+    }
+}
+
+fn error_variant(error: &BridgeError) -> &'static str {
+    match error {
+        BridgeError::RequestFailed(_) => "request_failed",
+        BridgeError::RequestFailedWithRetries(_) => "request_failed_with_retries",
+        BridgeError::JsonError(_) => "json_error",
+        BridgeError::BackendNotAvailable => "backend_not_available",
+        BridgeError::InvalidResponse(_) => "invalid_response",
+        BridgeError::BackendError(_) => "backend_error",
+    }
+}
+
+/// Build the middleware-wrapped HTTP client used for idempotent GETs.
+/// Connection errors and transient failures are retried with exponential
+/// backoff and jitter; the per-attempt timeout stays fixed regardless of the
+/// backoff schedule. POSTs use `build_write_client` instead, since retrying
+/// a non-idempotent request like `/organize/execute` risks double-running it.
+fn build_client(base_delay: Duration, max_delay: Duration, max_retries: u32) -> ClientWithMiddleware {
+    let retry_policy = ExponentialBackoff::builder()
+        .retry_bounds(base_delay, max_delay)
+        .build_with_max_retries(max_retries);
+
+    let inner = Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .expect("Failed to create HTTP client");
+
+    ClientBuilder::new(inner)
+        .with(RetryTransientMiddleware::new_with_policy(retry_policy))
+        .build()
+}
+
+/// Build the plain (non-retrying) HTTP client used for POSTs that must not
+/// be silently replayed on a transient failure.
+fn build_write_client() -> Client {
+    Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .expect("Failed to create HTTP client")
 }
\ No newline at end of file
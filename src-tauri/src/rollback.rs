@@ -0,0 +1,196 @@
+// Resumable rollback replay for organize runs.
+//
+// `OrganizeExecuteResponse.rollback_manifest` points at a journal of
+// per-file move intents: one JSON object per line, in the order each step
+// was attempted, with `completed` flipped once the move actually lands. The
+// journal is written (and `fsync`'d before each `OperationStep` executes) by
+// the Python FastAPI backend as part of `execute_organization` — that
+// process owns the actual file moves, so it's the only place that can record
+// an intent before performing one. Nothing in this crate needs, or should
+// grow, a second writer: this module only owns the Rust-side half of the
+// contract, reading the journal back and undoing it.
+//
+// Replaying walks the journal in reverse and moves each completed step's
+// file back from `target_path` to `source_path`, verifying a checksum first
+// so a file that's been touched again since the move isn't clobbered. A
+// step whose target no longer exists is assumed already rolled back and
+// skipped, so re-running a partially-applied rollback (e.g. after a crash
+// mid-replay) is always safe.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use thiserror::Error;
+use tracing::{debug, info, warn};
+
+use crate::types::RollbackResponse;
+
+/// A single per-file move intent recorded in a rollback manifest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub operation_id: String,
+    pub step_index: u32,
+    pub source_path: String,
+    pub target_path: String,
+    pub operation_type: String,
+    pub completed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+}
+
+#[derive(Error, Debug)]
+pub enum RollbackError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to parse journal entry: {0}")]
+    ParseError(#[from] serde_json::Error),
+    #[error("Rollback manifest not found: {0}")]
+    ManifestNotFound(String),
+    #[error("Rollback manifest is empty")]
+    EmptyManifest,
+}
+
+/// Read a rollback manifest's journal entries in the order they were
+/// written.
+pub fn read_journal(manifest_path: &str) -> Result<Vec<JournalEntry>, RollbackError> {
+    let path = Path::new(manifest_path);
+    if !path.exists() {
+        return Err(RollbackError::ManifestNotFound(manifest_path.to_string()));
+    }
+
+    let file = fs::File::open(path)?;
+    let reader = BufReader::new(file);
+    let mut entries = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        entries.push(serde_json::from_str(&line)?);
+    }
+    Ok(entries)
+}
+
+/// Replay a rollback manifest at `manifest_path`, restoring every step the
+/// journal marked `completed`. Calls `on_progress(done, total, bytes_done)`
+/// after each entry is processed, whether it was restored, skipped, or
+/// failed, so callers can drive a real `OperationProgress.bytes_processed`
+/// instead of a step count alone.
+pub fn replay_journal(
+    manifest_path: &str,
+    mut on_progress: impl FnMut(u32, u32, u64),
+) -> Result<RollbackResponse, RollbackError> {
+    let entries = read_journal(manifest_path)?;
+    let operation_id = entries
+        .first()
+        .map(|entry| entry.operation_id.clone())
+        .ok_or(RollbackError::EmptyManifest)?;
+
+    let total = entries.len() as u32;
+    let mut restored = 0u32;
+    let mut skipped = 0u32;
+    let mut failed = Vec::new();
+    let mut bytes_done = 0u64;
+
+    // Undo the most recent move first, in case an earlier step's target
+    // directory only exists because a later step (already reversed) left it
+    // behind.
+    for (done, entry) in entries.iter().rev().enumerate() {
+        match restore_entry(entry) {
+            Ok(Some(bytes)) => {
+                restored += 1;
+                bytes_done += bytes;
+            }
+            Ok(None) => skipped += 1,
+            Err(e) => {
+                warn!("Failed to roll back {}: {}", entry.target_path, e);
+                failed.push(entry.target_path.clone());
+            }
+        }
+        on_progress(done as u32 + 1, total, bytes_done);
+    }
+
+    let status = if failed.is_empty() { "completed" } else { "partial" };
+    info!(
+        "Rollback of {} finished: {} restored, {} skipped, {} failed",
+        manifest_path,
+        restored,
+        skipped,
+        failed.len()
+    );
+
+    Ok(RollbackResponse {
+        operation_id,
+        manifest_path: manifest_path.to_string(),
+        files_restored: restored,
+        files_skipped: skipped,
+        files_failed: failed,
+        status: status.to_string(),
+    })
+}
+
+/// Restore a single journal entry. Returns `Ok(Some(bytes))` with the
+/// restored file's size if it was moved back, `Ok(None)` if the step was
+/// skipped (never completed, or already rolled back), and `Err` if a
+/// checksum mismatch, a conflicting file at the source, or the move itself
+/// made it unsafe to proceed.
+pub(crate) fn restore_entry(entry: &JournalEntry) -> Result<Option<u64>, std::io::Error> {
+    if !entry.completed {
+        debug!(
+            "Skipping step {} ({}): never completed",
+            entry.step_index, entry.operation_type
+        );
+        return Ok(None);
+    }
+
+    let target = Path::new(&entry.target_path);
+    let source = Path::new(&entry.source_path);
+
+    if !target.exists() {
+        // Already rolled back, or the move never actually landed.
+        debug!(
+            "Skipping step {}: {} no longer exists",
+            entry.step_index, entry.target_path
+        );
+        return Ok(None);
+    }
+
+    let bytes = fs::metadata(target)?.len();
+
+    if let Some(expected) = &entry.checksum {
+        let actual = checksum_file(target)?;
+        if &actual != expected {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "checksum mismatch for {}, file changed since the move",
+                    entry.target_path
+                ),
+            ));
+        }
+    }
+
+    if source.exists() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!("refusing to overwrite existing file at {}", entry.source_path),
+        ));
+    }
+
+    if let Some(parent) = source.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::rename(target, source)?;
+    info!("Restored {} -> {}", entry.target_path, entry.source_path);
+    Ok(Some(bytes))
+}
+
+pub(crate) fn checksum_file(path: &Path) -> Result<String, std::io::Error> {
+    let bytes = fs::read(path)?;
+    let mut hasher = Sha256::new();
+    hasher.update(&bytes);
+    Ok(format!("{:x}", hasher.finalize()))
+}
@@ -0,0 +1,164 @@
+// User-overridable classification rules, evaluated with an embedded Rhai
+// engine before `preview_organization` asks the backend for its suggestion.
+//
+// Scripts live under the user config directory and are compiled once into an
+// `AST`; each scanned file is exposed to the script as a `FileInfo` and the
+// script's return value, if any, overrides the backend's classification.
+
+use rhai::{Engine, EvalAltResult, Scope, AST};
+use std::fs;
+use std::path::PathBuf;
+use thiserror::Error;
+use tracing::{debug, info, warn};
+
+#[derive(Error, Debug)]
+pub enum RuleError {
+    #[error("Failed to read rule script: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Rule script failed to compile: {0}")]
+    CompileError(String),
+    #[error("Rule script failed to evaluate: {0}")]
+    EvalError(String),
+    #[error("Could not determine the rules config directory")]
+    NoConfigDir,
+}
+
+/// Metadata about a scanned file or project, exposed to rule scripts.
+#[derive(Debug, Clone, rhai::CustomType)]
+#[rhai(extra = rhai_fields)]
+pub struct FileInfo {
+    pub path: String,
+    pub extension: String,
+    pub detected_language: String,
+    pub size_bytes: i64,
+}
+
+fn rhai_fields(builder: &mut rhai::TypeBuilder<FileInfo>) {
+    builder
+        .with_name("FileInfo")
+        .with_get("path", |info: &mut FileInfo| info.path.clone())
+        .with_get("extension", |info: &mut FileInfo| info.extension.clone())
+        .with_get("detected_language", |info: &mut FileInfo| info.detected_language.clone())
+        .with_get("size_bytes", |info: &mut FileInfo| info.size_bytes);
+}
+
+/// A single compiled rule, ready to be evaluated against `FileInfo` values.
+pub struct CompiledRule {
+    pub name: String,
+    ast: AST,
+}
+
+/// Loads, compiles, and evaluates user classification rules with a sandboxed
+/// engine: no filesystem or network access from scripts, and an operation
+/// limit so a runaway script can't hang the organization flow.
+pub struct RuleEngine {
+    engine: Engine,
+    rules: Vec<CompiledRule>,
+}
+
+impl RuleEngine {
+    pub fn new() -> Self {
+        let mut engine = Engine::new();
+        engine.set_max_operations(500_000);
+        engine.set_max_expr_depths(32, 32);
+        engine.set_max_string_size(64 * 1024);
+        engine.disable_symbol("import");
+        engine.build_type::<FileInfo>();
+
+        Self { engine, rules: Vec::new() }
+    }
+
+    /// Load every `*.rhai` script in the user's rules directory, compiling
+    /// each into an `AST` up front so a syntax error surfaces at load time
+    /// rather than mid-scan.
+    pub fn load_from_config_dir(&mut self) -> Result<(), RuleError> {
+        let dir = rules_dir()?;
+        if !dir.exists() {
+            debug!("Rules directory {:?} does not exist yet, skipping load", dir);
+            return Ok(());
+        }
+
+        self.rules.clear();
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("rhai") {
+                continue;
+            }
+
+            let name = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("unnamed")
+                .to_string();
+            let source = fs::read_to_string(&path)?;
+
+            match self.engine.compile(&source) {
+                Ok(ast) => {
+                    info!("Loaded classification rule: {}", name);
+                    self.rules.push(CompiledRule { name, ast });
+                }
+                Err(e) => {
+                    warn!("Rule script {} failed to compile: {}", name, e);
+                    return Err(RuleError::CompileError(format!("{}: {}", name, e)));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Compile `source` without registering it, so `validate_rule_script` can
+    /// report syntax errors without touching the active rule set.
+    pub fn validate(&self, source: &str) -> Result<(), RuleError> {
+        self.engine
+            .compile(source)
+            .map(|_| ())
+            .map_err(|e| RuleError::CompileError(e.to_string()))
+    }
+
+    /// Run every loaded rule against `file` in order, returning the first
+    /// non-empty category a rule returns. Later rules are not evaluated once
+    /// one produces an answer.
+    pub fn classify(&self, file: &FileInfo) -> Result<Option<String>, RuleError> {
+        for rule in &self.rules {
+            let mut scope = Scope::new();
+            let result: Result<String, Box<EvalAltResult>> =
+                self.engine.call_fn(&mut scope, &rule.ast, "classify", (file.clone(),));
+
+            match result {
+                Ok(category) if !category.is_empty() => return Ok(Some(category)),
+                Ok(_) => continue,
+                Err(e) => {
+                    warn!("Rule {} failed to evaluate: {}", rule.name, e);
+                    return Err(RuleError::EvalError(format!("{}: {}", rule.name, e)));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    pub fn loaded_rule_names(&self) -> Vec<String> {
+        self.rules.iter().map(|r| r.name.clone()).collect()
+    }
+
+    /// Compile and register a single rule from source, bypassing the config
+    /// directory scan. Used by tests that need a loaded rule without writing
+    /// a `.rhai` file to disk.
+    #[cfg(test)]
+    pub(crate) fn add_rule_from_source(&mut self, name: &str, source: &str) -> Result<(), RuleError> {
+        let ast = self
+            .engine
+            .compile(source)
+            .map_err(|e| RuleError::CompileError(e.to_string()))?;
+        self.rules.push(CompiledRule { name: name.to_string(), ast });
+        Ok(())
+    }
+}
+
+fn rules_dir() -> Result<PathBuf, RuleError> {
+    dirs::config_dir()
+        .map(|dir| dir.join("DevStewardAI").join("rules"))
+        .ok_or(RuleError::NoConfigDir)
+}
@@ -0,0 +1,195 @@
+// Typed, persisted application settings with hot-reload support.
+//
+// Settings are stored as TOML under the user config directory. `SettingsManager`
+// owns the in-memory copy behind a lock, and a `notify` filesystem watcher
+// reloads it whenever the file changes on disk (e.g. hand-edited while the
+// app is running) and emits a `settings-changed` event so open windows can
+// pick up the new values without a restart.
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::RwLock;
+use tracing::{debug, error, info, warn};
+
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::python_bridge::PythonBridge;
+use crate::types::{AppSettings, BackendMode};
+use crate::AppState;
+
+const SETTINGS_CHANGED_EVENT: &str = "settings-changed";
+
+#[derive(Error, Debug)]
+pub enum SettingsError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to parse settings file: {0}")]
+    ParseError(#[from] toml::de::Error),
+    #[error("Failed to serialize settings: {0}")]
+    SerializeError(#[from] toml::ser::Error),
+    #[error("Could not determine the settings config directory")]
+    NoConfigDir,
+    #[error("Failed to watch settings file: {0}")]
+    WatchError(#[from] notify::Error),
+}
+
+pub struct SettingsManager {
+    current: RwLock<AppSettings>,
+    path: PathBuf,
+}
+
+impl SettingsManager {
+    /// Load settings from disk, falling back to (and persisting) defaults if
+    /// no config file exists yet.
+    pub fn load() -> Result<Self, SettingsError> {
+        let path = settings_path()?;
+        let current = if path.exists() {
+            let raw = std::fs::read_to_string(&path)?;
+            toml::from_str(&raw)?
+        } else {
+            let defaults = AppSettings::default();
+            write_settings(&path, &defaults)?;
+            defaults
+        };
+
+        Ok(Self { current: RwLock::new(current), path })
+    }
+
+    pub async fn get(&self) -> AppSettings {
+        self.current.read().await.clone()
+    }
+
+    pub async fn save(&self, settings: AppSettings) -> Result<(), SettingsError> {
+        write_settings(&self.path, &settings)?;
+        *self.current.write().await = settings;
+        Ok(())
+    }
+
+    async fn reload_from_disk(&self) -> Result<(), SettingsError> {
+        let raw = std::fs::read_to_string(&self.path)?;
+        let settings: AppSettings = toml::from_str(&raw)?;
+        *self.current.write().await = settings;
+        Ok(())
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+}
+
+/// Apply the retry-related fields of `settings` to `bridge`. Called after
+/// load, save, and hot-reload so the bridge's backoff behavior never drifts
+/// from what's on disk.
+pub fn apply_to_bridge(settings: &AppSettings, bridge: &mut PythonBridge) {
+    bridge.set_base_retry_delay(Duration::from_millis(settings.bridge_base_retry_delay_ms));
+    bridge.set_max_retry_delay(Duration::from_millis(settings.bridge_max_retry_delay_ms));
+    bridge.set_max_retries(settings.bridge_max_retries);
+}
+
+/// Write `settings` to `path` via a temp-file-then-rename so a crash or full
+/// disk mid-write can't leave a truncated, unparseable settings file behind.
+/// The temp file is `fsync`'d before the rename so the rename itself can't
+/// land ahead of its data on a crash.
+fn write_settings(path: &PathBuf, settings: &AppSettings) -> Result<(), SettingsError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let serialized = toml::to_string_pretty(settings)?;
+
+    let tmp_path = tmp_path_for(path);
+    {
+        let mut file = std::fs::File::create(&tmp_path)?;
+        file.write_all(serialized.as_bytes())?;
+        file.sync_all()?;
+    }
+    std::fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+/// Sibling path used as the atomic-write staging file, e.g.
+/// `settings.toml` -> `.settings.toml.tmp`.
+fn tmp_path_for(path: &PathBuf) -> PathBuf {
+    let file_name = path.file_name().unwrap_or_default();
+    let mut tmp_name = std::ffi::OsString::from(".");
+    tmp_name.push(file_name);
+    tmp_name.push(".tmp");
+    path.with_file_name(tmp_name)
+}
+
+fn settings_path() -> Result<PathBuf, SettingsError> {
+    dirs::config_dir()
+        .map(|dir| dir.join("DevStewardAI").join("settings.toml"))
+        .ok_or(SettingsError::NoConfigDir)
+}
+
+/// Spawn a filesystem watcher on the settings file. On every write event the
+/// in-memory settings are reloaded and a `settings-changed` event is emitted
+/// with the fresh values, so external edits hot-reload without a restart.
+pub fn watch_for_changes(manager: Arc<SettingsManager>, app_handle: AppHandle) -> Result<RecommendedWatcher, SettingsError> {
+    let watch_path = manager.path().clone();
+    let (tx, rx) = std::sync::mpsc::channel();
+
+    let mut watcher: RecommendedWatcher = notify::recommended_watcher(tx)?;
+    watcher.watch(&watch_path, RecursiveMode::NonRecursive)?;
+
+    // `notify` only hands events to us via a blocking `std::sync::mpsc`
+    // receiver, and `rx.recv()` parks its thread until the next event. Run
+    // that receive loop on the blocking pool rather than inside a tokio task,
+    // so it can't permanently tie up a runtime worker thread; each event is
+    // then handed back to the async runtime to reload and emit.
+    tokio::task::spawn_blocking(move || {
+        while let Ok(event) = rx.recv() {
+            let manager = manager.clone();
+            let app_handle = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                handle_watch_event(event, manager, app_handle).await;
+            });
+        }
+    });
+
+    Ok(watcher)
+}
+
+async fn handle_watch_event(
+    event: notify::Result<notify::Event>,
+    manager: Arc<SettingsManager>,
+    app_handle: AppHandle,
+) {
+    let event = match event {
+        Ok(event) => event,
+        Err(e) => {
+            warn!("Settings watcher error: {}", e);
+            return;
+        }
+    };
+
+    if !event.kind.is_modify() {
+        return;
+    }
+
+    debug!("Settings file changed on disk, reloading");
+    match manager.reload_from_disk().await {
+        Ok(_) => {
+            let settings = manager.get().await;
+
+            let state: State<AppState> = app_handle.state();
+            let mut bridge = state.python_bridge.lock().await;
+            if settings.backend_mode == BackendMode::Remote {
+                bridge.set_base_url(settings.remote_backend_url.clone());
+            }
+            apply_to_bridge(&settings, &mut bridge);
+            drop(bridge);
+
+            if let Err(e) = app_handle.emit(SETTINGS_CHANGED_EVENT, &settings) {
+                error!("Failed to emit settings-changed event: {}", e);
+            } else {
+                info!("Settings reloaded from disk, bridge reconfigured");
+            }
+        }
+        Err(e) => warn!("Failed to reload settings after change: {}", e),
+    }
+}
@@ -0,0 +1,83 @@
+// Structured logging and metrics bootstrap.
+//
+// Mirrors the `init_tracing`/`init_metrics` split used by larger Rust
+// services: `init_tracing` wires up `tracing` (verbose span output only when
+// built with `--features debug`), and `init_metrics` installs a Prometheus
+// recorder that `get_metrics` renders for the diagnostics panel.
+
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+use tracing_subscriber::EnvFilter;
+
+use crate::types::{OrganizeExecuteResponse, OrganizePreviewResponse, ScanResponse};
+
+/// Initialize the global `tracing` subscriber. Release builds default to
+/// `info` level; the `debug` feature turns on full request/response spans.
+pub fn init_tracing() {
+    let default_filter = if cfg!(feature = "debug") {
+        "devsteward_ai=debug"
+    } else {
+        "devsteward_ai=info"
+    };
+
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default_filter));
+
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_target(false)
+        .init();
+}
+
+/// Install the process-wide Prometheus recorder and return a handle that can
+/// render the current metrics snapshot as text.
+pub fn init_metrics() -> PrometheusHandle {
+    PrometheusBuilder::new()
+        .install_recorder()
+        .expect("Failed to install Prometheus recorder")
+}
+
+/// Record workflow-level metrics for a completed scan: scan count, AI vs
+/// heuristic usage, confidence distribution, and scan duration. Lets a
+/// diagnostics panel show how much slower an AI-enabled scan is than a
+/// heuristic one.
+pub fn record_scan(scan: &ScanResponse) {
+    let method = scan.final_classification.method.as_str();
+    metrics::counter!("scans_total", "method" => method.to_string()).increment(1);
+    metrics::histogram!("scan_classification_confidence", "method" => method.to_string())
+        .record(scan.final_classification.confidence);
+    metrics::histogram!("scan_duration_ms").record(scan.scan_duration_ms as f64);
+
+    if scan.ai_classification.is_some() {
+        metrics::counter!("scans_ai_enabled_total").increment(1);
+    }
+}
+
+/// Record metrics for a generated organization preview: operation count,
+/// conflicts found, and the backend's own time estimate.
+pub fn record_organize_preview(preview: &OrganizePreviewResponse) {
+    metrics::counter!("organize_previews_total").increment(1);
+    metrics::counter!("organize_conflicts_total").increment(preview.conflicts_found as u64);
+    metrics::histogram!("organize_estimated_time_seconds").record(preview.estimated_time_seconds);
+}
+
+/// Record metrics for an organization run as it's kicked off. Bytes/files
+/// moved and end-to-end duration are recorded once by
+/// `record_organize_progress_terminal` when the run finishes, since
+/// execution now runs asynchronously in `jobs::run_worker`.
+pub fn record_organize_execute(response: &OrganizeExecuteResponse) {
+    metrics::counter!("organize_executions_total", "status" => response.status.clone()).increment(1);
+}
+
+/// Record the end of an organization run once the worker sees a terminal
+/// status, including the files/bytes actually moved and total duration.
+pub fn record_organize_progress_terminal(progress: &crate::types::OperationProgress) {
+    metrics::counter!("organize_runs_finished_total", "status" => progress.status.clone()).increment(1);
+    metrics::counter!("organize_files_moved_total").increment(progress.files_processed as u64);
+    metrics::counter!("organize_bytes_moved_total").increment(progress.bytes_processed);
+    metrics::histogram!("organize_duration_seconds").record(progress.elapsed_time_seconds);
+}
+
+/// Record a Python backend restart made by the supervisor. Manual stop/start
+/// cycles are not counted.
+pub fn record_backend_restart() {
+    metrics::counter!("backend_restarts_total").increment(1);
+}
@@ -3,10 +3,26 @@ mod tests {
     use super::*;
     use crate::python_bridge::PythonBridge;
     use crate::process_manager::ProcessManager;
+    use crate::rollback::{self, JournalEntry};
+    use crate::rules::{FileInfo, RuleEngine};
     use crate::types::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
     use std::sync::Arc;
     use tokio::sync::Mutex;
 
+    /// Returns a fresh path under the system temp dir for this test process,
+    /// so parallel tests don't collide on the same file.
+    fn temp_path(label: &str) -> std::path::PathBuf {
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+        let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!(
+            "devsteward_ai_test_{}_{}_{}",
+            std::process::id(),
+            n,
+            label
+        ))
+    }
+
     /// Test that PythonBridge can be created
     #[test]
     fn test_python_bridge_creation() {
@@ -21,6 +37,24 @@ mod tests {
         assert_eq!(manager.get_port(), 8008);
     }
 
+    /// A process that never started isn't eligible for the supervisor's
+    /// auto-restart — there's nothing to restart.
+    #[test]
+    fn test_process_manager_not_restart_eligible_before_start() {
+        let manager = ProcessManager::new();
+        assert!(!manager.is_restart_eligible_for_test());
+    }
+
+    /// Once started, a crash (detected as `start_time` going `None` while
+    /// still expected to be running) must leave the manager restart-eligible
+    /// rather than being mistaken for "never started".
+    #[test]
+    fn test_process_manager_restart_eligible_after_crash() {
+        let mut manager = ProcessManager::new();
+        manager.simulate_started_then_crashed_for_test();
+        assert!(manager.is_restart_eligible_for_test());
+    }
+
     /// Test AppSettings default values
     #[test]
     fn test_app_settings_defaults() {
@@ -68,11 +102,15 @@ mod tests {
             port: 8008,
             uptime_seconds: Some(120),
             health_status: Some("healthy".to_string()),
+            restart_count: 0,
+            terminal_failure_reason: None,
         };
 
         assert!(status.is_running);
         assert_eq!(status.pid, Some(12345));
         assert_eq!(status.port, 8008);
+        assert_eq!(status.restart_count, 0);
+        assert!(status.terminal_failure_reason.is_none());
     }
 
     /// Test error serialization
@@ -104,4 +142,177 @@ mod tests {
         assert_eq!(template.category, "SystemUtilities");
         assert_eq!(template.features.len(), 2);
     }
+
+    /// A completed step whose target file is untouched restores cleanly.
+    #[test]
+    fn test_rollback_restore_entry_restores_completed_step() {
+        let target = temp_path("restore_target");
+        let source = temp_path("restore_source");
+        std::fs::write(&target, b"hello").unwrap();
+
+        let entry = JournalEntry {
+            operation_id: "op-1".to_string(),
+            step_index: 0,
+            source_path: source.to_string_lossy().to_string(),
+            target_path: target.to_string_lossy().to_string(),
+            operation_type: "move".to_string(),
+            completed: true,
+            checksum: Some(rollback::checksum_file(&target).unwrap()),
+        };
+
+        assert_eq!(rollback::restore_entry(&entry).unwrap(), Some(5));
+        assert!(source.exists());
+        assert!(!target.exists());
+
+        std::fs::remove_file(&source).ok();
+    }
+
+    /// A step the journal never marked `completed` is skipped, not restored.
+    #[test]
+    fn test_rollback_restore_entry_skips_uncompleted_step() {
+        let target = temp_path("uncompleted_target");
+        let source = temp_path("uncompleted_source");
+        std::fs::write(&target, b"hello").unwrap();
+
+        let entry = JournalEntry {
+            operation_id: "op-1".to_string(),
+            step_index: 0,
+            source_path: source.to_string_lossy().to_string(),
+            target_path: target.to_string_lossy().to_string(),
+            operation_type: "move".to_string(),
+            completed: false,
+            checksum: None,
+        };
+
+        assert_eq!(rollback::restore_entry(&entry).unwrap(), None);
+        assert!(target.exists());
+
+        std::fs::remove_file(&target).ok();
+    }
+
+    /// A file that changed since the move is left alone rather than clobbered.
+    #[test]
+    fn test_rollback_restore_entry_checksum_mismatch_errors() {
+        let target = temp_path("mismatch_target");
+        let source = temp_path("mismatch_source");
+        std::fs::write(&target, b"original").unwrap();
+        let stale_checksum = rollback::checksum_file(&target).unwrap();
+        std::fs::write(&target, b"changed since the move").unwrap();
+
+        let entry = JournalEntry {
+            operation_id: "op-1".to_string(),
+            step_index: 0,
+            source_path: source.to_string_lossy().to_string(),
+            target_path: target.to_string_lossy().to_string(),
+            operation_type: "move".to_string(),
+            completed: true,
+            checksum: Some(stale_checksum),
+        };
+
+        assert!(rollback::restore_entry(&entry).is_err());
+        assert!(target.exists());
+
+        std::fs::remove_file(&target).ok();
+    }
+
+    /// A step whose target no longer exists is assumed already rolled back.
+    #[test]
+    fn test_rollback_restore_entry_skips_missing_target() {
+        let target = temp_path("missing_target");
+        let source = temp_path("missing_source");
+
+        let entry = JournalEntry {
+            operation_id: "op-1".to_string(),
+            step_index: 0,
+            source_path: source.to_string_lossy().to_string(),
+            target_path: target.to_string_lossy().to_string(),
+            operation_type: "move".to_string(),
+            completed: true,
+            checksum: None,
+        };
+
+        assert_eq!(rollback::restore_entry(&entry).unwrap(), None);
+    }
+
+    /// A loaded rule's return value overrides the default classification.
+    #[test]
+    fn test_rule_engine_classify_returns_rule_category() {
+        let mut engine = RuleEngine::new();
+        engine
+            .add_rule_from_source(
+                "force_archive",
+                r#"fn classify(file) { if file.extension == "zip" { "Archive" } else { "" } }"#,
+            )
+            .unwrap();
+
+        let file = FileInfo {
+            path: "/tmp/bundle.zip".to_string(),
+            extension: "zip".to_string(),
+            detected_language: "unknown".to_string(),
+            size_bytes: 0,
+        };
+
+        assert_eq!(engine.classify(&file).unwrap(), Some("Archive".to_string()));
+    }
+
+    /// A rule that returns an empty string defers to the next rule (or the
+    /// backend's own classification if none match).
+    #[test]
+    fn test_rule_engine_classify_empty_result_defers() {
+        let mut engine = RuleEngine::new();
+        engine
+            .add_rule_from_source("noop", r#"fn classify(file) { "" }"#)
+            .unwrap();
+
+        let file = FileInfo {
+            path: "/tmp/readme.md".to_string(),
+            extension: "md".to_string(),
+            detected_language: "unknown".to_string(),
+            size_bytes: 0,
+        };
+
+        assert_eq!(engine.classify(&file).unwrap(), None);
+    }
+
+    /// Valid rule scripts compile without being registered.
+    #[test]
+    fn test_rule_engine_validate_accepts_valid_script() {
+        let engine = RuleEngine::new();
+        assert!(engine.validate(r#"fn classify(file) { "Docs" }"#).is_ok());
+    }
+
+    /// Syntax errors are reported instead of panicking.
+    #[test]
+    fn test_rule_engine_validate_rejects_invalid_script() {
+        let engine = RuleEngine::new();
+        assert!(engine.validate("fn classify(file) { this is not rhai").is_err());
+    }
+
+    /// When the configured port is free, it's used as-is.
+    #[test]
+    fn test_find_available_port_uses_configured_port_when_free() {
+        let mut manager = ProcessManager::new();
+        let listener = std::net::TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let free_port = listener.local_addr().unwrap().port();
+        drop(listener);
+
+        manager.set_port(free_port);
+        assert_eq!(manager.find_available_port().unwrap(), free_port);
+    }
+
+    /// When the configured port is taken, the next free port in range is used.
+    #[test]
+    fn test_find_available_port_falls_back_when_taken() {
+        let listener = std::net::TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let taken_port = listener.local_addr().unwrap().port();
+
+        let mut manager = ProcessManager::new();
+        manager.set_port(taken_port);
+        let chosen = manager.find_available_port().unwrap();
+
+        assert_ne!(chosen, taken_port);
+        assert!(chosen >= taken_port);
+
+        drop(listener);
+    }
 }
\ No newline at end of file
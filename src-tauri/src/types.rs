@@ -128,8 +128,26 @@ pub struct OrganizeExecuteResponse {
     pub timestamp: String,
 }
 
-/// Progress tracking for organization operations
+/// Request to replay a rollback manifest produced by a previous
+/// `OrganizeExecuteResponse.rollback_manifest`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RollbackRequest {
+    pub manifest_path: String,
+}
+
+/// Response from replaying a rollback manifest
 #[derive(Debug, Serialize, Deserialize)]
+pub struct RollbackResponse {
+    pub operation_id: String,
+    pub manifest_path: String,
+    pub files_restored: u32,
+    pub files_skipped: u32,
+    pub files_failed: Vec<String>,
+    pub status: String,
+}
+
+/// Progress tracking for organization operations
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OperationProgress {
     pub operation_id: String,
     pub current_step: u32,
@@ -146,8 +164,19 @@ pub struct OperationProgress {
     pub error_message: Option<String>,
 }
 
+/// Where the Python backend process lives.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BackendMode {
+    /// Spawn and own a local uvicorn child process (the default).
+    Local,
+    /// Connect to an already-running backend at `remote_backend_url` instead
+    /// of spawning one, e.g. a heavier AI/scan backend running on another
+    /// machine or in a container.
+    Remote,
+}
+
 /// Application settings
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppSettings {
     pub organization_root: String,
     pub default_ai_model: String,
@@ -157,6 +186,22 @@ pub struct AppSettings {
     pub ollama_base_url: String,
     pub python_backend_port: u16,
     pub auto_start_backend: bool,
+    /// Whether scan/organize workflow metrics are recorded and exposed via
+    /// `get_metrics`. Bridge-level request metrics are unaffected by this flag.
+    pub enable_workflow_metrics: bool,
+    pub backend_mode: BackendMode,
+    /// Base URL of the remote backend when `backend_mode` is `Remote`, e.g.
+    /// `http://192.168.1.50:8008`. Ignored in `Local` mode.
+    pub remote_backend_url: String,
+    /// Base delay, in milliseconds, for the bridge's exponential retry
+    /// backoff. See `PythonBridge::set_base_retry_delay`.
+    pub bridge_base_retry_delay_ms: u64,
+    /// Cap, in milliseconds, applied to each backoff delay. See
+    /// `PythonBridge::set_max_retry_delay`.
+    pub bridge_max_retry_delay_ms: u64,
+    /// How many times a transient bridge request is retried. See
+    /// `PythonBridge::set_max_retries`.
+    pub bridge_max_retries: u32,
 }
 
 impl Default for AppSettings {
@@ -170,6 +215,12 @@ impl Default for AppSettings {
             ollama_base_url: "http://localhost:11434".to_string(),
             python_backend_port: 8008,
             auto_start_backend: true,
+            enable_workflow_metrics: true,
+            backend_mode: BackendMode::Local,
+            remote_backend_url: "http://127.0.0.1:8008".to_string(),
+            bridge_base_retry_delay_ms: 200,
+            bridge_max_retry_delay_ms: 5000,
+            bridge_max_retries: 5,
         }
     }
 }
@@ -182,6 +233,15 @@ pub struct ProcessStatus {
     pub port: u16,
     pub uptime_seconds: Option<u64>,
     pub health_status: Option<String>,
+    /// How many times the supervisor has auto-restarted the backend since
+    /// the last time it ran without crashing for a full minute. Lets the UI
+    /// show e.g. "backend restarting (attempt 3)".
+    pub restart_count: u32,
+    /// Set once the supervisor has given up auto-restarting after
+    /// `SUPERVISOR_MAX_RESTARTS` consecutive restarts have each crashed again, explaining why the
+    /// backend is down and won't come back on its own.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub terminal_failure_reason: Option<String>,
 }
 
 /// Ollama models response